@@ -0,0 +1,108 @@
+use anyhow::Result;
+
+use crate::github::{GitHubClient, ReleaseInfo};
+use crate::release_notes::PullRequestInfo;
+
+/// A release host that breezy can drive.
+///
+/// Both github.com and API-compatible servers such as Gitea expose the same
+/// `/repos/{owner}/{repo}/releases` surface, so the draft-reconciliation logic
+/// in `select_draft_releases` only needs these operations and stays unaware of
+/// which host it is talking to.
+pub trait ReleaseBackend {
+    fn list_all_releases(&self, per_page: u32) -> Result<Vec<ReleaseInfo>>;
+
+    fn fetch_merged_pull_requests(
+        &self,
+        branch: &str,
+        since: Option<&str>,
+        per_page: u32,
+    ) -> Result<Vec<PullRequestInfo>>;
+
+    fn create_release(
+        &self,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()>;
+
+    fn update_release(
+        &self,
+        release_id: u64,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()>;
+
+    fn delete_release(&self, release_id: u64) -> Result<()>;
+
+    /// Return the repository-relative paths changed by pull request `number`,
+    /// used to scope a PR to the monorepo packages it touched.
+    fn fetch_changed_files(&self, number: u64) -> Result<Vec<String>>;
+}
+
+impl ReleaseBackend for GitHubClient {
+    fn list_all_releases(&self, per_page: u32) -> Result<Vec<ReleaseInfo>> {
+        GitHubClient::list_all_releases(self, per_page)
+    }
+
+    fn fetch_merged_pull_requests(
+        &self,
+        branch: &str,
+        since: Option<&str>,
+        per_page: u32,
+    ) -> Result<Vec<PullRequestInfo>> {
+        GitHubClient::fetch_merged_pull_requests(self, branch, since, per_page)
+    }
+
+    fn create_release(
+        &self,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()> {
+        GitHubClient::create_release(self, tag_name, name, body, target_commitish)
+    }
+
+    fn update_release(
+        &self,
+        release_id: u64,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()> {
+        GitHubClient::update_release(self, release_id, tag_name, name, body, target_commitish)
+    }
+
+    fn delete_release(&self, release_id: u64) -> Result<()> {
+        GitHubClient::delete_release(self, release_id)
+    }
+
+    fn fetch_changed_files(&self, number: u64) -> Result<Vec<String>> {
+        GitHubClient::fetch_changed_files(self, number)
+    }
+}
+
+/// Which release host the `server` input selects; Gitea additionally reads its
+/// API root from the `api-url` input.
+#[derive(Debug, Clone)]
+pub enum Server {
+    GitHub,
+    Gitea,
+}
+
+impl Server {
+    /// Parse the `server` input; anything empty or `github` maps to GitHub,
+    /// `gitea` (or `self-hosted`) maps to Gitea.
+    pub fn parse(value: Option<&str>) -> Result<Self> {
+        match value.map(str::trim).map(str::to_lowercase).as_deref() {
+            None | Some("") | Some("github") => Ok(Server::GitHub),
+            Some("gitea") | Some("self-hosted") => Ok(Server::Gitea),
+            Some(other) => anyhow::bail!("Unknown server '{other}'; expected github or gitea."),
+        }
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::{Context, Result, anyhow, bail};
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::package_trie::PackageTrie;
+
 const DEFAULT_CHANGE_TEMPLATE: &str = "$TITLE";
 const DEFAULT_CATEGORY_HEADING_LEVEL: u8 = 2;
 
@@ -13,6 +16,12 @@ pub struct ReleaseCategory {
     pub labels: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct PackageConfig {
+    pub name: String,
+    pub root: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct ReleaseConfig {
     pub language: Option<String>,
@@ -22,6 +31,32 @@ pub struct ReleaseConfig {
     pub exclude_labels: Vec<String>,
     pub change_template: String,
     pub template: Option<String>,
+    pub format: Option<String>,
+    pub packages: Vec<PackageConfig>,
+    pub version_resolver: VersionResolver,
+}
+
+/// Maps PR labels to the semantic-version component they bump. Used when the
+/// `bump-from-labels` input derives the next version from merged PRs instead of
+/// reading it from project files.
+#[derive(Debug, Clone, Default)]
+pub struct VersionResolver {
+    pub major: Vec<String>,
+    pub minor: Vec<String>,
+    pub patch: Vec<String>,
+}
+
+impl ReleaseConfig {
+    /// Build a prefix trie of the configured package roots for longest-prefix
+    /// file assignment. The trie is derived on demand so `ReleaseConfig` stays
+    /// cheap to clone.
+    pub fn package_trie(&self) -> PackageTrie {
+        let mut trie = PackageTrie::new();
+        for package in &self.packages {
+            trie.insert(&package.root, &package.name);
+        }
+        trie
+    }
 }
 
 #[derive(Deserialize)]
@@ -37,6 +72,17 @@ struct RawConfig {
     #[serde(rename = "change-template")]
     change_template: Option<String>,
     template: Option<String>,
+    format: Option<String>,
+    packages: Option<BTreeMap<String, String>>,
+    #[serde(rename = "version-resolver")]
+    version_resolver: Option<RawVersionResolver>,
+}
+
+#[derive(Deserialize)]
+struct RawVersionResolver {
+    major: Option<Vec<String>>,
+    minor: Option<Vec<String>>,
+    patch: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
@@ -92,6 +138,27 @@ impl ReleaseConfig {
                 .filter(|value| !value.is_empty())
                 .unwrap_or_else(|| DEFAULT_CHANGE_TEMPLATE.to_string()),
             template: raw.template.map(|value| value.trim().to_string()),
+            format: raw
+                .format
+                .map(|value| value.trim().to_lowercase())
+                .filter(|value| !value.is_empty()),
+            packages: raw
+                .packages
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(name, root)| PackageConfig {
+                    name: name.trim().to_string(),
+                    root: root.trim().trim_matches('/').to_string(),
+                })
+                .collect(),
+            version_resolver: raw
+                .version_resolver
+                .map(|resolver| VersionResolver {
+                    major: normalize_labels(resolver.major.unwrap_or_default()),
+                    minor: normalize_labels(resolver.minor.unwrap_or_default()),
+                    patch: normalize_labels(resolver.patch.unwrap_or_default()),
+                })
+                .unwrap_or_default(),
         })
     }
 }
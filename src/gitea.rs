@@ -0,0 +1,245 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use crate::backend::ReleaseBackend;
+use crate::github::ReleaseInfo;
+use crate::release_notes::PullRequestInfo;
+
+/// A client for a self-hosted Gitea instance.
+///
+/// Gitea mirrors GitHub's releases surface (`GET/POST/PATCH/DELETE
+/// /repos/{owner}/{repo}/releases`) but authenticates with a plain
+/// `Authorization: token <TOKEN>` header and lives under a user-supplied API
+/// root such as `https://git.example.com/api/v1`.
+pub struct GiteaClient {
+    agent: ureq::Agent,
+    api_url: String,
+    token: String,
+    owner: String,
+    repo: String,
+}
+
+#[derive(Deserialize)]
+struct RawRelease {
+    id: u64,
+    draft: bool,
+    body: Option<String>,
+    created_at: String,
+    published_at: Option<String>,
+    target_commitish: String,
+}
+
+#[derive(Deserialize)]
+struct RawPull {
+    number: u64,
+    title: String,
+    merged_at: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    user: Option<RawUser>,
+    #[serde(default)]
+    labels: Vec<RawLabel>,
+}
+
+#[derive(Deserialize)]
+struct RawUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct RawChangedFile {
+    filename: String,
+}
+
+#[derive(Deserialize)]
+struct RawLabel {
+    name: String,
+}
+
+impl GiteaClient {
+    pub fn new(api_url: &str, token: &str, owner: &str, repo: &str) -> Result<Self> {
+        Ok(Self {
+            agent: ureq::agent(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    fn repo_url(&self, suffix: &str) -> String {
+        format!("{}/repos/{}/{}{}", self.api_url, self.owner, self.repo, suffix)
+    }
+
+    fn auth_header(&self) -> String {
+        format!("token {}", self.token)
+    }
+}
+
+impl ReleaseBackend for GiteaClient {
+    fn list_all_releases(&self, per_page: u32) -> Result<Vec<ReleaseInfo>> {
+        let mut releases = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let url = self.repo_url("/releases");
+            let raw: Vec<RawRelease> = self
+                .agent
+                .get(&url)
+                .set("Authorization", &self.auth_header())
+                .query("limit", &per_page.to_string())
+                .query("page", &page.to_string())
+                .call()
+                .with_context(|| format!("Failed to list releases from {url}"))?
+                .into_json()
+                .map_err(|error| anyhow!("Invalid releases payload: {error}"))?;
+
+            let count = raw.len();
+            releases.extend(raw.into_iter().map(|release| ReleaseInfo {
+                id: release.id,
+                draft: release.draft,
+                body: release.body,
+                created_at: release.created_at,
+                published_at: release.published_at,
+                target_commitish: release.target_commitish,
+            }));
+
+            if count < per_page as usize {
+                break;
+            }
+            page += 1;
+        }
+        Ok(releases)
+    }
+
+    fn fetch_merged_pull_requests(
+        &self,
+        branch: &str,
+        since: Option<&str>,
+        per_page: u32,
+    ) -> Result<Vec<PullRequestInfo>> {
+        let mut pulls = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let url = self.repo_url("/pulls");
+            let raw: Vec<RawPull> = self
+                .agent
+                .get(&url)
+                .set("Authorization", &self.auth_header())
+                .query("state", "closed")
+                .query("base", branch)
+                .query("sort", "recentupdate")
+                .query("limit", &per_page.to_string())
+                .query("page", &page.to_string())
+                .call()
+                .with_context(|| format!("Failed to list pull requests from {url}"))?
+                .into_json()
+                .map_err(|error| anyhow!("Invalid pull request payload: {error}"))?;
+
+            let count = raw.len();
+            for pull in raw {
+                let Some(merged_at) = pull.merged_at else {
+                    continue;
+                };
+                if let Some(since) = since {
+                    if merged_at.as_str() <= since {
+                        continue;
+                    }
+                }
+                pulls.push(PullRequestInfo {
+                    number: pull.number,
+                    title: pull.title,
+                    merged_at: Some(merged_at),
+                    labels: pull.labels.into_iter().map(|label| label.name).collect(),
+                    author: pull.user.map(|user| user.login).unwrap_or_default(),
+                    url: pull.html_url,
+                });
+            }
+
+            if count < per_page as usize {
+                break;
+            }
+            page += 1;
+        }
+        Ok(pulls)
+    }
+
+    fn create_release(
+        &self,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()> {
+        let url = self.repo_url("/releases");
+        self.agent
+            .post(&url)
+            .set("Authorization", &self.auth_header())
+            .send_json(ureq::json!({
+                "tag_name": tag_name,
+                "name": name,
+                "body": body,
+                "target_commitish": target_commitish,
+                "draft": true,
+            }))
+            .with_context(|| format!("Failed to create release at {url}"))?;
+        Ok(())
+    }
+
+    fn update_release(
+        &self,
+        release_id: u64,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()> {
+        let url = self.repo_url(&format!("/releases/{release_id}"));
+        self.agent
+            .request("PATCH", &url)
+            .set("Authorization", &self.auth_header())
+            .send_json(ureq::json!({
+                "tag_name": tag_name,
+                "name": name,
+                "body": body,
+                "target_commitish": target_commitish,
+                "draft": true,
+            }))
+            .with_context(|| format!("Failed to update release at {url}"))?;
+        Ok(())
+    }
+
+    fn delete_release(&self, release_id: u64) -> Result<()> {
+        let url = self.repo_url(&format!("/releases/{release_id}"));
+        self.agent
+            .delete(&url)
+            .set("Authorization", &self.auth_header())
+            .call()
+            .with_context(|| format!("Failed to delete release at {url}"))?;
+        Ok(())
+    }
+
+    fn fetch_changed_files(&self, number: u64) -> Result<Vec<String>> {
+        let mut files = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let url = self.repo_url(&format!("/pulls/{number}/files"));
+            let raw: Vec<RawChangedFile> = self
+                .agent
+                .get(&url)
+                .set("Authorization", &self.auth_header())
+                .query("page", &page.to_string())
+                .call()
+                .with_context(|| format!("Failed to list changed files from {url}"))?
+                .into_json()
+                .map_err(|error| anyhow!("Invalid changed files payload: {error}"))?;
+
+            let count = raw.len();
+            files.extend(raw.into_iter().map(|file| file.filename));
+            if count == 0 {
+                break;
+            }
+            page += 1;
+        }
+        Ok(files)
+    }
+}
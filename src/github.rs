@@ -0,0 +1,303 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use crate::release_notes::PullRequestInfo;
+
+const API_ROOT: &str = "https://api.github.com";
+const USER_AGENT: &str = "breezy";
+
+/// A release as reported by the host, trimmed to the fields breezy reconciles
+/// against.
+#[derive(Clone, Debug)]
+pub struct ReleaseInfo {
+    pub id: u64,
+    pub draft: bool,
+    pub body: Option<String>,
+    pub created_at: String,
+    pub published_at: Option<String>,
+    pub target_commitish: String,
+}
+
+/// A client for github.com (or a GitHub Enterprise instance sharing the same
+/// REST semantics), authenticating with a `Authorization: token <TOKEN>`
+/// header.
+pub struct GitHubClient {
+    agent: ureq::Agent,
+    token: String,
+    owner: String,
+    repo: String,
+}
+
+#[derive(Deserialize)]
+struct RawRelease {
+    id: u64,
+    draft: bool,
+    body: Option<String>,
+    created_at: String,
+    published_at: Option<String>,
+    target_commitish: String,
+}
+
+#[derive(Deserialize)]
+struct RawPull {
+    number: u64,
+    title: String,
+    merged_at: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    user: Option<RawUser>,
+    #[serde(default)]
+    labels: Vec<RawLabel>,
+}
+
+#[derive(Deserialize)]
+struct RawUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct RawLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct RawChangedFile {
+    filename: String,
+}
+
+/// Map a raw GitHub pull request into breezy's `PullRequestInfo`, carrying its
+/// labels, author and URL. Returns `None` for a PR that was closed without
+/// being merged.
+fn pull_from_raw(raw: RawPull) -> Option<PullRequestInfo> {
+    let merged_at = raw.merged_at?;
+    Some(PullRequestInfo {
+        number: raw.number,
+        title: raw.title,
+        merged_at: Some(merged_at),
+        labels: raw.labels.into_iter().map(|label| label.name).collect(),
+        author: raw.user.map(|user| user.login).unwrap_or_default(),
+        url: raw.html_url,
+    })
+}
+
+impl GitHubClient {
+    pub fn new(token: &str, owner: &str, repo: &str) -> Result<Self> {
+        Ok(Self {
+            agent: ureq::agent(),
+            token: token.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    fn repo_url(&self, suffix: &str) -> String {
+        format!("{API_ROOT}/repos/{}/{}{}", self.owner, self.repo, suffix)
+    }
+
+    fn auth_header(&self) -> String {
+        format!("token {}", self.token)
+    }
+
+    pub fn list_all_releases(&self, per_page: u32) -> Result<Vec<ReleaseInfo>> {
+        let mut releases = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let url = self.repo_url("/releases");
+            let raw: Vec<RawRelease> = self
+                .agent
+                .get(&url)
+                .set("Authorization", &self.auth_header())
+                .set("User-Agent", USER_AGENT)
+                .query("per_page", &per_page.to_string())
+                .query("page", &page.to_string())
+                .call()
+                .with_context(|| format!("Failed to list releases from {url}"))?
+                .into_json()
+                .map_err(|error| anyhow!("Invalid releases payload: {error}"))?;
+
+            let count = raw.len();
+            releases.extend(raw.into_iter().map(|release| ReleaseInfo {
+                id: release.id,
+                draft: release.draft,
+                body: release.body,
+                created_at: release.created_at,
+                published_at: release.published_at,
+                target_commitish: release.target_commitish,
+            }));
+
+            if count < per_page as usize {
+                break;
+            }
+            page += 1;
+        }
+        Ok(releases)
+    }
+
+    pub fn fetch_merged_pull_requests(
+        &self,
+        branch: &str,
+        since: Option<&str>,
+        per_page: u32,
+    ) -> Result<Vec<PullRequestInfo>> {
+        let mut pulls = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let url = self.repo_url("/pulls");
+            let raw: Vec<RawPull> = self
+                .agent
+                .get(&url)
+                .set("Authorization", &self.auth_header())
+                .set("User-Agent", USER_AGENT)
+                .query("state", "closed")
+                .query("base", branch)
+                .query("sort", "updated")
+                .query("direction", "desc")
+                .query("per_page", &per_page.to_string())
+                .query("page", &page.to_string())
+                .call()
+                .with_context(|| format!("Failed to list pull requests from {url}"))?
+                .into_json()
+                .map_err(|error| anyhow!("Invalid pull request payload: {error}"))?;
+
+            let count = raw.len();
+            for pull in raw {
+                let Some(pull) = pull_from_raw(pull) else {
+                    continue;
+                };
+                if let (Some(since), Some(merged_at)) = (since, pull.merged_at.as_deref()) {
+                    if merged_at <= since {
+                        continue;
+                    }
+                }
+                pulls.push(pull);
+            }
+
+            if count < per_page as usize {
+                break;
+            }
+            page += 1;
+        }
+        Ok(pulls)
+    }
+
+    pub fn create_release(
+        &self,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()> {
+        let url = self.repo_url("/releases");
+        self.agent
+            .post(&url)
+            .set("Authorization", &self.auth_header())
+            .set("User-Agent", USER_AGENT)
+            .send_json(ureq::json!({
+                "tag_name": tag_name,
+                "name": name,
+                "body": body,
+                "target_commitish": target_commitish,
+                "draft": true,
+            }))
+            .with_context(|| format!("Failed to create release at {url}"))?;
+        Ok(())
+    }
+
+    pub fn update_release(
+        &self,
+        release_id: u64,
+        tag_name: &str,
+        name: &str,
+        body: &str,
+        target_commitish: &str,
+    ) -> Result<()> {
+        let url = self.repo_url(&format!("/releases/{release_id}"));
+        self.agent
+            .request("PATCH", &url)
+            .set("Authorization", &self.auth_header())
+            .set("User-Agent", USER_AGENT)
+            .send_json(ureq::json!({
+                "tag_name": tag_name,
+                "name": name,
+                "body": body,
+                "target_commitish": target_commitish,
+                "draft": true,
+            }))
+            .with_context(|| format!("Failed to update release at {url}"))?;
+        Ok(())
+    }
+
+    pub fn delete_release(&self, release_id: u64) -> Result<()> {
+        let url = self.repo_url(&format!("/releases/{release_id}"));
+        self.agent
+            .delete(&url)
+            .set("Authorization", &self.auth_header())
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .with_context(|| format!("Failed to delete release at {url}"))?;
+        Ok(())
+    }
+
+    pub fn fetch_changed_files(&self, number: u64) -> Result<Vec<String>> {
+        let mut files = Vec::new();
+        let mut page = 1u32;
+        loop {
+            let url = self.repo_url(&format!("/pulls/{number}/files"));
+            let raw: Vec<RawChangedFile> = self
+                .agent
+                .get(&url)
+                .set("Authorization", &self.auth_header())
+                .set("User-Agent", USER_AGENT)
+                .query("per_page", "100")
+                .query("page", &page.to_string())
+                .call()
+                .with_context(|| format!("Failed to list changed files from {url}"))?
+                .into_json()
+                .map_err(|error| anyhow!("Invalid changed files payload: {error}"))?;
+
+            let count = raw.len();
+            files.extend(raw.into_iter().map(|file| file.filename));
+            if count == 0 {
+                break;
+            }
+            page += 1;
+        }
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn propagates_labels_author_and_url() {
+        let raw: RawPull = serde_json::from_value(serde_json::json!({
+            "number": 7,
+            "title": "Add widget",
+            "merged_at": "2024-01-02T03:04:05Z",
+            "html_url": "https://github.com/acme/repo/pull/7",
+            "user": { "login": "octocat" },
+            "labels": [{ "name": "feature" }, { "name": "ui" }],
+        }))
+        .unwrap();
+
+        let pull = pull_from_raw(raw).expect("merged PR maps to Some");
+        assert_eq!(pull.labels, vec!["feature".to_string(), "ui".to_string()]);
+        assert_eq!(pull.author, "octocat");
+        assert_eq!(pull.url, "https://github.com/acme/repo/pull/7");
+    }
+
+    #[test]
+    fn skips_unmerged_pull_requests() {
+        let raw: RawPull = serde_json::from_value(serde_json::json!({
+            "number": 8,
+            "title": "Abandoned",
+            "merged_at": null,
+            "html_url": "https://github.com/acme/repo/pull/8",
+        }))
+        .unwrap();
+
+        assert!(pull_from_raw(raw).is_none());
+    }
+}
@@ -0,0 +1,100 @@
+use crate::template::expand;
+
+/// Convert the subset of Markdown breezy emits into HTML.
+///
+/// Release bodies only ever contain ATX headings (`## Title`), optional `- `
+/// bullet lines, and plain change lines, so a line-based converter is enough:
+/// headings become `<h1>`..`<h6>`, runs of bullet lines become `<ul>` blocks,
+/// and any other non-empty line becomes a `<p>`. The leading
+/// `<!-- breezy:branch=... -->` marker comment is dropped.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut in_list = false;
+
+    let close_list = |html: &mut String, in_list: &mut bool| {
+        if *in_list {
+            html.push_str("</ul>\n");
+            *in_list = false;
+        }
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("<!--") {
+            close_list(&mut html, &mut in_list);
+            continue;
+        }
+
+        if let Some(level) = heading_level(trimmed) {
+            close_list(&mut html, &mut in_list);
+            let text = trimmed[level..].trim();
+            html.push_str(&format!("<h{level}>{}</h{level}>\n", escape(text)));
+        } else if let Some(item) = trimmed.strip_prefix("- ") {
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("  <li>{}</li>\n", escape(item)));
+        } else {
+            close_list(&mut html, &mut in_list);
+            html.push_str(&format!("<p>{}</p>\n", escape(trimmed)));
+        }
+    }
+
+    close_list(&mut html, &mut in_list);
+    html
+}
+
+/// Wrap `changelog` HTML in `template`, expanding `$CHANGELOG` plus the
+/// release-level `$VERSION`/`$TAG`/`$DATE` placeholders.
+pub fn render_template(
+    template: &str,
+    changelog: &str,
+    version: &str,
+    tag: &str,
+    date: &str,
+) -> String {
+    expand(template, |name| match name {
+        "CHANGELOG" => Some(changelog.to_string()),
+        "VERSION" => Some(version.to_string()),
+        "TAG" => Some(tag.to_string()),
+        "DATE" => Some(date.to_string()),
+        _ => None,
+    })
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&hashes) && line[hashes..].starts_with(' ') {
+        Some(hashes)
+    } else {
+        None
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_headings_and_bullets() {
+        let markdown = "<!-- breezy:branch=main -->\n\n## Features\n- Add thing\n- Fix <other>";
+        let html = markdown_to_html(markdown);
+        assert_eq!(
+            html,
+            "<h2>Features</h2>\n<ul>\n  <li>Add thing</li>\n  <li>Fix &lt;other&gt;</li>\n</ul>\n"
+        );
+    }
+
+    #[test]
+    fn substitutes_template_placeholders() {
+        let rendered = render_template("<h1>$VERSION</h1>$CHANGELOG", "<p>x</p>", "1.2.3", "v1.2.3", "2024-01-01");
+        assert_eq!(rendered, "<h1>1.2.3</h1><p>x</p>");
+    }
+}
@@ -1,11 +1,27 @@
+mod backend;
+mod config;
+mod gitea;
 mod github;
+mod html;
+mod package_trie;
 mod release_notes;
+mod semver;
+mod template;
 mod version;
 
 use anyhow::{anyhow, bail, Context, Result};
+use backend::{ReleaseBackend, Server};
+use config::{load_config, ReleaseConfig, VersionResolver};
+use gitea::GiteaClient;
 use github::ReleaseInfo;
-use release_notes::{build_release_notes, release_marker};
+use release_notes::{
+    build_release_notes, package_release_marker, release_marker, PullRequestInfo,
+};
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use template::expand;
 use version::{parse_languages, resolve_version};
 
 const MAX_PER_PAGE: u32 = 100;
@@ -15,6 +31,39 @@ struct DraftSelection {
     extras: Vec<u64>,
 }
 
+/// Settings for the optional HTML changelog artifact written alongside the
+/// Markdown release body.
+struct HtmlSettings {
+    path: PathBuf,
+    template: String,
+    version: String,
+    date: String,
+}
+
+impl HtmlSettings {
+    /// Derive settings for a package by inserting its name into the output file
+    /// stem, so per-package runs don't clobber a single artifact.
+    fn for_package(&self, package: &str) -> HtmlSettings {
+        let stem = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let extension = self
+            .path
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+        let file_name = format!("{stem}-{package}{extension}");
+        HtmlSettings {
+            path: self.path.with_file_name(file_name),
+            template: self.template.clone(),
+            version: self.version.clone(),
+            date: self.date.clone(),
+        }
+    }
+}
+
 fn main() {
     if let Err(error) = run() {
         eprintln!("{error}");
@@ -40,22 +89,20 @@ fn run() -> Result<()> {
     }
 
     let cwd = env::current_dir().context("Unable to resolve current working directory.")?;
+    let config = load_config(read_input("config"), &cwd)?;
     let version_info = resolve_version(&cwd, &languages)?;
 
-    let tag_name = format!("{}{}", tag_prefix.trim(), version_info.version);
-    let release_name = format!("{tag_name} ({branch})");
-    let marker = release_marker(&branch);
-
     let (owner, repo) = parse_repository()?;
-    let client = github::GitHubClient::new(&token, &owner, &repo)?;
+    let server = Server::parse(read_input("server").as_deref())?;
+    let client: Box<dyn ReleaseBackend> = match server {
+        Server::GitHub => Box::new(github::GitHubClient::new(&token, &owner, &repo)?),
+        Server::Gitea => {
+            let api_url = required_input("api-url")?;
+            Box::new(GiteaClient::new(&api_url, &token, &owner, &repo)?)
+        }
+    };
 
     let releases = client.list_all_releases(MAX_PER_PAGE)?;
-    let selection = select_draft_releases(&releases, &marker);
-
-    for release_id in selection.extras {
-        client.delete_release(release_id)?;
-        println!("Deleted extra draft release {release_id} for {branch}");
-    }
 
     let since = select_latest_published_release(&releases, &branch)
         .map(|release| release.published_at.as_deref().unwrap_or(&release.created_at))
@@ -63,25 +110,269 @@ fn run() -> Result<()> {
 
     let pull_requests =
         client.fetch_merged_pull_requests(&branch, since.as_deref(), MAX_PER_PAGE)?;
-    let release_notes = build_release_notes(&marker, &pull_requests);
 
-    if let Some(release_id) = selection.primary {
-        client.update_release(
-            release_id,
+    let version = resolve_release_version(&version_info.version, config.as_ref(), &pull_requests);
+
+    let default_tag = format!("{}{}", tag_prefix.trim(), version);
+    let date = today();
+    let tag_name = render_release(
+        config.as_ref().and_then(|config| config.tag_template.as_deref()),
+        &default_tag,
+        &version,
+        &default_tag,
+        &branch,
+        &date,
+    );
+    let default_name = format!("{tag_name} ({branch})");
+    let release_name = render_release(
+        config.as_ref().and_then(|config| config.name_template.as_deref()),
+        &default_name,
+        &version,
+        &tag_name,
+        &branch,
+        &date,
+    );
+
+    let html = resolve_html_settings(config.as_ref(), &cwd, &version, &date)?;
+
+    let packages = config
+        .as_ref()
+        .map(|config| config.packages.as_slice())
+        .unwrap_or(&[]);
+
+    if packages.is_empty() {
+        let marker = release_marker(&branch);
+        reconcile_release(
+            client.as_ref(),
+            &releases,
+            &marker,
             &tag_name,
             &release_name,
-            &release_notes,
             &branch,
+            &pull_requests,
+            config.as_ref(),
+            html.as_ref(),
         )?;
+        return Ok(());
+    }
+
+    let config_ref = config.as_ref().expect("packages imply config");
+    let trie = config_ref.package_trie();
+    let mut per_package: BTreeMap<&str, Vec<PullRequestInfo>> = BTreeMap::new();
+    for pull_request in &pull_requests {
+        let files = client.fetch_changed_files(pull_request.number)?;
+        let mut owners = BTreeSet::new();
+        for path in files {
+            if let Some(package) = trie.longest_match(&path) {
+                owners.insert(package.to_string());
+            }
+        }
+        for owner in owners {
+            if let Some(package) = packages.iter().find(|package| package.name == owner) {
+                per_package
+                    .entry(package.name.as_str())
+                    .or_default()
+                    .push(pull_request.clone());
+            }
+        }
+    }
+
+    for package in packages {
+        let marker = package_release_marker(&branch, &package.name);
+        let package_prs = per_package.remove(package.name.as_str()).unwrap_or_default();
+        let tag_name = format!("{tag_name}-{}", package.name);
+        let release_name = format!("{release_name} ({})", package.name);
+        let package_html = html.as_ref().map(|html| html.for_package(&package.name));
+        reconcile_release(
+            client.as_ref(),
+            &releases,
+            &marker,
+            &tag_name,
+            &release_name,
+            &branch,
+            &package_prs,
+            config.as_ref(),
+            package_html.as_ref(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Reconcile the single draft release identified by `marker`: delete any extra
+/// drafts, then update the primary one or create a fresh draft.
+#[allow(clippy::too_many_arguments)]
+fn reconcile_release(
+    client: &dyn ReleaseBackend,
+    releases: &[ReleaseInfo],
+    marker: &str,
+    tag_name: &str,
+    release_name: &str,
+    branch: &str,
+    pull_requests: &[PullRequestInfo],
+    config: Option<&ReleaseConfig>,
+    html: Option<&HtmlSettings>,
+) -> Result<()> {
+    let selection = select_draft_releases(releases, marker);
+
+    for release_id in selection.extras {
+        client.delete_release(release_id)?;
+        println!("Deleted extra draft release {release_id} for {branch}");
+    }
+
+    let release_notes = build_release_notes(marker, branch, pull_requests, config);
+
+    if let Some(html) = html {
+        let body = html::markdown_to_html(&release_notes);
+        let rendered =
+            html::render_template(&html.template, &body, &html.version, tag_name, &html.date);
+        fs::write(&html.path, rendered)
+            .with_context(|| format!("Failed to write HTML changelog to {}", html.path.display()))?;
+        println!("Wrote HTML changelog to {}", html.path.display());
+    }
+
+    if let Some(release_id) = selection.primary {
+        client.update_release(release_id, tag_name, release_name, &release_notes, branch)?;
         println!("Updated draft release {release_id} for {branch}");
     } else {
-        client.create_release(&tag_name, &release_name, &release_notes, &branch)?;
+        client.create_release(tag_name, release_name, &release_notes, branch)?;
         println!("Created draft release for {branch}");
     }
 
     Ok(())
 }
 
+const DEFAULT_HTML_TEMPLATE: &str = "$CHANGELOG";
+
+/// Resolve the optional HTML artifact settings from the `output-html` input and
+/// the `format`/`template` config. Returns `None` when HTML output is not
+/// requested; errors when `format: html` is set but no output path is given.
+fn resolve_html_settings(
+    config: Option<&ReleaseConfig>,
+    cwd: &Path,
+    version: &str,
+    date: &str,
+) -> Result<Option<HtmlSettings>> {
+    let output = read_input("output-html")
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let format_html = config
+        .and_then(|config| config.format.as_deref())
+        .map(|format| format == "html")
+        .unwrap_or(false);
+
+    let path = match (output, format_html) {
+        (Some(path), _) => cwd.join(path),
+        (None, true) => bail!("format: html requires an output-html path to write the artifact."),
+        (None, false) => return Ok(None),
+    };
+
+    let template = match config.and_then(|config| config.template.as_deref()) {
+        Some(value) => {
+            let candidate = cwd.join(value);
+            if candidate.is_file() {
+                fs::read_to_string(&candidate).with_context(|| {
+                    format!("Failed to read HTML template {}", candidate.display())
+                })?
+            } else {
+                value.to_string()
+            }
+        }
+        None => DEFAULT_HTML_TEMPLATE.to_string(),
+    };
+
+    Ok(Some(HtmlSettings {
+        path,
+        template,
+        version: version.to_string(),
+        date: date.to_string(),
+    }))
+}
+
+/// Resolve the version to release. When the `bump-from-labels` input is set the
+/// next version is derived from the labels of `pull_requests` via the configured
+/// `version-resolver`; otherwise the file-based `current` version is used as-is.
+fn resolve_release_version(
+    current: &str,
+    config: Option<&ReleaseConfig>,
+    pull_requests: &[PullRequestInfo],
+) -> String {
+    if !input_flag("bump-from-labels") {
+        return current.to_string();
+    }
+
+    let default_resolver = VersionResolver::default();
+    let resolver = config
+        .map(|config| &config.version_resolver)
+        .unwrap_or(&default_resolver);
+
+    // With `bump-from-labels` on, a release always advances at least a patch:
+    // breaking→major, feature/minor→minor, otherwise patch. An empty/default
+    // `version-resolver` (also used when no config is loaded) maps nothing to
+    // major/minor, so it is effectively a plain patch bump.
+    let bump = semver::bump_for(resolver, pull_requests).unwrap_or(semver::Bump::Patch);
+    semver::apply_bump(current, bump)
+}
+
+/// Interpret an action input as a boolean flag (`true`/`1`/`yes`/`on`).
+fn input_flag(name: &str) -> bool {
+    matches!(
+        read_input(name)
+            .map(|value| value.trim().to_lowercase())
+            .as_deref(),
+        Some("true" | "1" | "yes" | "on")
+    )
+}
+
+/// Expand a release-level template (`name-template`/`tag-template`) against the
+/// `$VERSION`, `$TAG`, `$BRANCH`, `$DATE` context, falling back to `default`
+/// when no template is configured.
+fn render_release(
+    template: Option<&str>,
+    default: &str,
+    version: &str,
+    tag: &str,
+    branch: &str,
+    date: &str,
+) -> String {
+    match template.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(template) => expand(template, |name| match name {
+            "VERSION" => Some(version.to_string()),
+            "TAG" => Some(tag.to_string()),
+            "BRANCH" => Some(branch.to_string()),
+            "DATE" => Some(date.to_string()),
+            _ => None,
+        }),
+        None => default.to_string(),
+    }
+}
+
+/// Current UTC date formatted as `YYYY-MM-DD`, used for the `$DATE` placeholder.
+fn today() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let days = (secs / 86_400) as i64;
+
+    // Convert a Unix day number to a civil (year, month, day) using Howard
+    // Hinnant's algorithm.
+    let z = days + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let year = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = if month <= 2 { year + 1 } else { year };
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
 fn input_key(name: &str) -> String {
     format!("INPUT_{}", name.replace(' ', "_").to_uppercase())
 }
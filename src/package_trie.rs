@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+/// A prefix trie over `/`-separated path components used to assign changed
+/// files to packages.
+///
+/// Each package root (e.g. `crates/foo`) is inserted as a path of components;
+/// looking a file path up walks the trie as far as it matches and returns the
+/// package registered at the deepest node reached, giving O(path length)
+/// longest-prefix assignment regardless of how many packages are configured.
+#[derive(Default)]
+pub struct PackageTrie {
+    node: Node,
+}
+
+#[derive(Default)]
+struct Node {
+    package: Option<String>,
+    children: BTreeMap<String, Node>,
+}
+
+fn components(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+impl PackageTrie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `package` at `root`, e.g. `crates/foo`.
+    pub fn insert(&mut self, root: &str, package: &str) {
+        let mut node = &mut self.node;
+        for segment in components(root) {
+            node = node.children.entry(segment.to_string()).or_default();
+        }
+        node.package = Some(package.to_string());
+    }
+
+    /// Return the package owning `path`, i.e. the one whose root is the longest
+    /// prefix of `path`, or `None` when no root matches.
+    pub fn longest_match(&self, path: &str) -> Option<&str> {
+        let mut node = &self.node;
+        let mut found = node.package.as_deref();
+        for segment in components(path) {
+            match node.children.get(segment) {
+                Some(child) => {
+                    node = child;
+                    if let Some(package) = node.package.as_deref() {
+                        found = Some(package);
+                    }
+                }
+                None => break,
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trie() -> PackageTrie {
+        let mut trie = PackageTrie::new();
+        trie.insert("crates/foo", "foo");
+        trie.insert("crates/foo/internal", "foo-internal");
+        trie.insert("crates/bar", "bar");
+        trie
+    }
+
+    #[test]
+    fn assigns_longest_prefix() {
+        let trie = trie();
+        assert_eq!(trie.longest_match("crates/foo/src/lib.rs"), Some("foo"));
+        assert_eq!(
+            trie.longest_match("crates/foo/internal/mod.rs"),
+            Some("foo-internal")
+        );
+        assert_eq!(trie.longest_match("crates/bar/Cargo.toml"), Some("bar"));
+    }
+
+    #[test]
+    fn unmatched_paths_return_none() {
+        let trie = trie();
+        assert_eq!(trie.longest_match("docs/readme.md"), None);
+    }
+}
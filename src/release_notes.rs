@@ -1,41 +1,245 @@
 use std::collections::HashSet;
 
+use crate::config::ReleaseConfig;
+use crate::template::expand;
+
 #[derive(Clone, Debug)]
 pub struct PullRequestInfo {
     pub number: u64,
     pub title: String,
     pub merged_at: Option<String>,
+    pub labels: Vec<String>,
+    pub author: String,
+    pub url: String,
 }
 
 pub fn release_marker(branch: &str) -> String {
     format!("<!-- breezy:branch={branch} -->")
 }
 
+/// Package-qualified marker used when emitting one draft release per package in
+/// a monorepo, e.g. `<!-- breezy:branch=main package=foo -->`.
+pub fn package_release_marker(branch: &str, package: &str) -> String {
+    format!("<!-- breezy:branch={branch} package={package} -->")
+}
+
 fn sort_by_merge_date(pull_requests: &[PullRequestInfo]) -> Vec<PullRequestInfo> {
     let mut ordered = pull_requests.to_vec();
     ordered.sort_by(|left, right| left.merged_at.cmp(&right.merged_at));
     ordered
 }
 
-pub fn build_release_notes(marker: &str, pull_requests: &[PullRequestInfo]) -> String {
-    let mut lines = vec![marker.to_string()];
+fn normalized_labels(pull_request: &PullRequestInfo) -> Vec<String> {
+    pull_request
+        .labels
+        .iter()
+        .map(|label| label.trim().to_lowercase())
+        .filter(|label| !label.is_empty())
+        .collect()
+}
+
+fn heading(level: u8, title: &str) -> String {
+    format!("{} {}", "#".repeat(level.max(1) as usize), title)
+}
+
+/// Render a single change line for `pull_request` by expanding `template`
+/// against the per-PR placeholder context (`$TITLE`, `$NUMBER`, `$AUTHOR`,
+/// `$URL`, `$BRANCH`).
+fn render_change(template: &str, pull_request: &PullRequestInfo, branch: &str) -> String {
+    expand(template, |name| match name {
+        "TITLE" => Some(pull_request.title.clone()),
+        "NUMBER" => Some(pull_request.number.to_string()),
+        "AUTHOR" => Some(pull_request.author.clone()),
+        "URL" => Some(pull_request.url.clone()),
+        "BRANCH" => Some(branch.to_string()),
+        _ => None,
+    })
+}
+
+/// Build the Markdown release body for `pull_requests`, bucketing them into the
+/// categories declared in `config` when one is supplied.
+///
+/// Labels are normalized to lowercase and PRs intersecting the config's
+/// `exclude_labels` are dropped. When `config` is `None` (or carries no
+/// categories) the remaining PRs are emitted as a flat list. Otherwise each PR
+/// is placed under the first category whose labels it matches;
+/// leftovers fall into a trailing "Uncategorized" section. The merge-date sort
+/// is preserved within every section and the `<!-- breezy:branch=... -->` marker
+/// is always the first line.
+pub fn build_release_notes(
+    marker: &str,
+    branch: &str,
+    pull_requests: &[PullRequestInfo],
+    config: Option<&ReleaseConfig>,
+) -> String {
+    let ordered = sort_by_merge_date(pull_requests);
     let mut seen = HashSet::new();
+    let exclude_labels = config.map(|config| config.exclude_labels.as_slice()).unwrap_or(&[]);
+    let change_template = config
+        .map(|config| config.change_template.as_str())
+        .unwrap_or("$TITLE");
 
-    for pull_request in sort_by_merge_date(pull_requests) {
-        if seen.contains(&pull_request.number) {
+    let mut deduped = Vec::new();
+    for pull_request in ordered {
+        if !seen.insert(pull_request.number) {
+            continue;
+        }
+        let labels = normalized_labels(&pull_request);
+        if labels.iter().any(|label| exclude_labels.contains(label)) {
             continue;
         }
-        seen.insert(pull_request.number);
-        lines.push(pull_request.title);
+        let change = render_change(change_template, &pull_request, branch);
+        deduped.push((labels, change));
     }
 
-    if lines.len() == 1 {
-        return lines.remove(0);
+    let categories = config.map(|config| config.categories.as_slice()).unwrap_or(&[]);
+    let mut lines = Vec::new();
+
+    if categories.is_empty() {
+        for (_, change) in &deduped {
+            lines.push(change.clone());
+        }
+    } else {
+        let mut remaining: Vec<bool> = vec![true; deduped.len()];
+        for category in categories {
+            let mut section: Vec<String> = Vec::new();
+            for (index, (labels, change)) in deduped.iter().enumerate() {
+                if !remaining[index] {
+                    continue;
+                }
+                if category.labels.iter().any(|label| labels.contains(label)) {
+                    remaining[index] = false;
+                    section.push(change.clone());
+                }
+            }
+            if !section.is_empty() {
+                push_section(&mut lines, &heading(category.heading_level, &category.title), section);
+            }
+        }
+
+        let uncategorized: Vec<String> = deduped
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| remaining[*index])
+            .map(|(_, (_, change))| change.clone())
+            .collect();
+        if !uncategorized.is_empty() {
+            push_section(&mut lines, &heading(DEFAULT_HEADING_LEVEL, UNCATEGORIZED_TITLE), uncategorized);
+        }
     }
 
-    let mut body = Vec::with_capacity(lines.len() + 1);
-    body.push(lines.remove(0));
+    if lines.is_empty() {
+        return marker.to_string();
+    }
+
+    let mut body = Vec::with_capacity(lines.len() + 2);
+    body.push(marker.to_string());
     body.push(String::new());
     body.extend(lines);
     body.join("\n")
 }
+
+const DEFAULT_HEADING_LEVEL: u8 = 2;
+const UNCATEGORIZED_TITLE: &str = "Uncategorized";
+
+fn push_section(lines: &mut Vec<String>, heading: &str, mut changes: Vec<String>) {
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(heading.to_string());
+    lines.append(&mut changes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ReleaseCategory, ReleaseConfig};
+
+    fn pr(number: u64, title: &str, merged_at: &str, labels: &[&str]) -> PullRequestInfo {
+        PullRequestInfo {
+            number,
+            title: title.to_string(),
+            merged_at: Some(merged_at.to_string()),
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+            author: String::new(),
+            url: String::new(),
+        }
+    }
+
+    fn category(title: &str, labels: &[&str]) -> ReleaseCategory {
+        ReleaseCategory {
+            title: title.to_string(),
+            heading_level: 2,
+            labels: labels.iter().map(|label| label.to_string()).collect(),
+        }
+    }
+
+    fn config(categories: Vec<ReleaseCategory>, exclude_labels: &[&str]) -> ReleaseConfig {
+        ReleaseConfig {
+            language: None,
+            tag_template: None,
+            name_template: None,
+            categories,
+            exclude_labels: exclude_labels.iter().map(|label| label.to_string()).collect(),
+            change_template: "$TITLE".to_string(),
+            template: None,
+            format: None,
+            packages: Vec::new(),
+            version_resolver: Default::default(),
+        }
+    }
+
+    #[test]
+    fn places_pr_in_first_matching_category_only() {
+        let config = config(
+            vec![
+                category("Features", &["feature"]),
+                category("Fixes", &["fix"]),
+            ],
+            &[],
+        );
+        let prs = [pr(1, "Both", "2024-01-01", &["feature", "fix"])];
+
+        let notes = build_release_notes("MARK", "main", &prs, Some(&config));
+
+        assert_eq!(notes, "MARK\n\n## Features\nBoth");
+    }
+
+    #[test]
+    fn drops_pull_requests_with_excluded_labels() {
+        let config = config(vec![category("Features", &["feature"])], &["skip-changelog"]);
+        let prs = [
+            pr(1, "Kept", "2024-01-01", &["feature"]),
+            pr(2, "Hidden", "2024-01-02", &["feature", "skip-changelog"]),
+        ];
+
+        let notes = build_release_notes("MARK", "main", &prs, Some(&config));
+
+        assert_eq!(notes, "MARK\n\n## Features\nKept");
+    }
+
+    #[test]
+    fn unmatched_pull_requests_fall_into_uncategorized() {
+        let config = config(vec![category("Features", &["feature"])], &[]);
+        let prs = [
+            pr(1, "A feature", "2024-01-01", &["feature"]),
+            pr(2, "Something else", "2024-01-02", &["chore"]),
+        ];
+
+        let notes = build_release_notes("MARK", "main", &prs, Some(&config));
+
+        assert_eq!(
+            notes,
+            "MARK\n\n## Features\nA feature\n\n## Uncategorized\nSomething else"
+        );
+    }
+
+    #[test]
+    fn empty_pull_request_set_yields_marker_only() {
+        let config = config(vec![category("Features", &["feature"])], &[]);
+
+        let notes = build_release_notes("MARK", "main", &[], Some(&config));
+
+        assert_eq!(notes, "MARK");
+    }
+}
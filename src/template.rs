@@ -0,0 +1,95 @@
+/// Expand `$`-prefixed placeholders in `template` against `lookup`.
+///
+/// Both the bare `$VAR` form and the braced `${VAR}` form are recognized; the
+/// braced form lets callers disambiguate a placeholder from trailing text (for
+/// example `$VERSIONrc`). A placeholder name is the longest run of ASCII
+/// alphanumerics and underscores following the `$`. Unknown placeholders, and a
+/// literal `$` not introducing a placeholder, are left verbatim so user
+/// templates never silently lose characters.
+pub fn expand(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let bytes = template.as_bytes();
+    let mut out = String::with_capacity(template.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] != b'$' {
+            // Copy the literal character verbatim; `index` is always on a char
+            // boundary (the `$` marker and everything we skip is ASCII), so a
+            // multi-byte UTF-8 char is preserved intact rather than truncated.
+            let ch = template[index..].chars().next().expect("char boundary");
+            out.push(ch);
+            index += ch.len_utf8();
+            continue;
+        }
+
+        let rest = &template[index + 1..];
+        let (name, consumed, braced) = if rest.starts_with('{') {
+            match rest[1..].find('}') {
+                Some(end) => (&rest[1..1 + end], 1 + end + 1, true),
+                None => ("", 0, false),
+            }
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], end, false)
+        };
+
+        if !braced && name.is_empty() {
+            out.push('$');
+            index += 1;
+            continue;
+        }
+
+        match lookup(name) {
+            Some(value) => out.push_str(&value),
+            None => {
+                out.push('$');
+                if braced {
+                    out.push('{');
+                    out.push_str(name);
+                    out.push('}');
+                } else {
+                    out.push_str(name);
+                }
+            }
+        }
+        index += 1 + consumed;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> + '_ {
+        move |name: &str| {
+            pairs
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn expands_bare_and_braced_placeholders() {
+        let lookup = ctx(&[("TITLE", "Fix bug"), ("NUMBER", "42")]);
+        assert_eq!(expand("$TITLE (#$NUMBER)", &lookup), "Fix bug (#42)");
+        assert_eq!(expand("${TITLE}!", &lookup), "Fix bug!");
+    }
+
+    #[test]
+    fn preserves_non_ascii_literals() {
+        let lookup = ctx(&[("NAME", "café")]);
+        assert_eq!(expand("© $NAME — déjà", &lookup), "© café — déjà");
+    }
+
+    #[test]
+    fn leaves_unknown_tokens_verbatim() {
+        let lookup = ctx(&[("TAG", "v1.0.0")]);
+        assert_eq!(expand("$TAG $MISSING ${NOPE}", &lookup), "v1.0.0 $MISSING ${NOPE}");
+        assert_eq!(expand("cost is $5", &lookup), "cost is $5");
+    }
+}